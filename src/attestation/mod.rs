@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod se;
+pub mod sev;
+pub mod sgx;
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `se` module exports the Message types required for both the
+//! client and server components of the IBM Secure Execution (s390x)
+//! remote attestation protocol.
+//!
+//! Secure Execution has no launch-start secret injection step the way
+//! SEV does. Instead, the verifier issues an attestation *request* that
+//! the guest passes to the s390 ultravisor, and the ultravisor returns a
+//! measurement tag computed over the guest configuration UID and the
+//! nonce supplied in the request, so the exchange cannot be replayed.
+
+use serde::{Deserialize, Serialize};
+
+/// The `Finish` struct signals a successful Secure Execution attestation.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Finish;
+
+/// A CBOR-encoded attestation message.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Message {
+    /// The attestation request blob that the s390 ultravisor consumes.
+    /// `host_key_hashes` are the digests of the host keys the verifier
+    /// trusts, `measurement_key` is the key the ultravisor should use
+    /// to compute the response HMAC, and `nonce` is folded into that
+    /// HMAC so a captured response cannot be replayed against a later
+    /// request.
+    AttestationRequest {
+        nonce: Vec<u8>,
+        host_key_hashes: Vec<[u8; 32]>,
+        measurement_key: Vec<u8>,
+    },
+
+    /// The HMAC-SHA512 measurement the ultravisor computes over the
+    /// booted guest configuration and the request's nonce. The verifier
+    /// recomputes the expected tag from the known host-key hashes and
+    /// the nonce it issued and compares it to `measurement_tag`.
+    /// `config_uid` identifies the booted image.
+    AttestationResponse {
+        measurement_tag: Vec<u8>,
+        additional_data: Vec<u8>,
+        config_uid: [u8; 16],
+    },
+
+    /// The finish message signals a successful attestation.
+    Finish(Finish),
+}
@@ -8,8 +8,8 @@
 
 //taken wholesale for https://github.com/connorkuehl/koine/commit/8659386bbdce554872231636d00a4b94c69f3aa2
 use serde::{Deserialize, Serialize};
-use sev::certs::Chain;
-use sev::launch;
+use sev::certs::sev::Chain;
+use sev::launch::sev as launch;
 use sev::Build;
 
 /// The `Finish` struct contains useful information regarding the launch
@@ -21,10 +21,14 @@ pub struct Finish;
 /// a `sev` crate `Build` type with a `sev` crate `Measurement` type.
 ///
 /// The `build` and `measurement` fields are CBOR-encoded structures.
+/// `nonce` must equal the value the manager issued in `Challenge` so the
+/// manager can be sure this measurement was produced after the
+/// challenge was issued, rather than replayed from an earlier session.
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Measurement {
     pub build: Build,
     pub measurement: launch::Measurement,
+    pub nonce: [u8; 32],
 }
 
 /// A CBOR-encoded attestation message.
@@ -41,6 +45,13 @@ pub enum Message {
     /// CA certificates (using 512 byte components).
     CertificateChainRome(Chain),
 
+    /// The challenge carries a fresh, random nonce that the manager
+    /// issues immediately upon receiving the tenant's certificate
+    /// chain. The tenant must fold this nonce into the `Measurement`
+    /// it returns so the manager can detect replay of a recorded
+    /// measurement transcript.
+    Challenge { nonce: [u8; 32] },
+
     /// The launch start buffer establishes a secure channel with
     /// the remote SEV platform and furnishes with information
     /// that the tenant has tailored to match their expectations
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `sgx` module exports the Message types required for both the
+//! client and server components of the remote Intel SGX (DCAP) quote
+//! based attestation protocol.
+
+use serde::{Deserialize, Serialize};
+
+/// The `Finish` struct signals a successful attestation.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Finish;
+
+/// A CBOR-encoded attestation message.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Message {
+    /// The target info binds the quote to a specific quoting enclave so
+    /// the attesting enclave can establish a local report against it.
+    TargetInfo(Vec<u8>),
+
+    /// The ECDSA quote: report body, signature, and PCK certificate
+    /// chain, all produced by the quoting enclave.
+    Quote { quote: Vec<u8> },
+
+    /// The verification material pulled from the PCCS that the
+    /// verifier needs to check the quote's certificate chain and TCB
+    /// level.
+    Collateral {
+        tcb_info: Vec<u8>,
+        qe_identity: Vec<u8>,
+        pck_crl: Vec<u8>,
+    },
+
+    /// The finish message signals a successful attestation.
+    Finish(Finish),
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `keychain` module authenticates the CBOR control messages koine
+//! exchanges between tenants and Keep managers. Without it, a rogue
+//! party on the path could forge a [`crate::KeepContract`] advertisement
+//! or swap a [`crate::Workload`]'s wasm binary. A [`SignedEnvelope`]
+//! wraps a payload together with a compact secp256k1 ECDSA signature
+//! over its CBOR serialization and the signer's recoverable public key, so the
+//! receiving side can confirm authenticity before trusting the payload.
+
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+use sha2::{Digest, Sha256};
+
+/// A keypair used to sign outgoing control messages.
+pub struct Keychain {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+/// The reason a [`SignedEnvelope`] failed to verify.
+#[derive(Debug, Eq, PartialEq)]
+pub enum KeychainError {
+    /// The payload couldn't be serialized to canonical CBOR for hashing.
+    Encode,
+    /// The signature doesn't match the payload and claimed public key.
+    InvalidSignature,
+    /// The recovered public key isn't present in the caller's allowlist.
+    UntrustedSigner,
+}
+
+/// A payload plus the compact ECDSA signature and recoverable public
+/// key of whoever signed it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedEnvelope<T> {
+    pub payload: T,
+    #[serde(with = "BigArray")]
+    pub pubkey: [u8; 33],
+    #[serde(with = "BigArray")]
+    pub signature: [u8; 64],
+    pub recovery_id: u8,
+}
+
+impl Keychain {
+    /// Generate a new random keypair.
+    pub fn generate() -> Self {
+        let secp = Secp256k1::new();
+        let mut rng = rand::thread_rng();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rng);
+        Keychain {
+            secret_key,
+            public_key,
+        }
+    }
+
+    /// The compressed, 33-byte public key for this keychain.
+    pub fn public_key(&self) -> [u8; 33] {
+        self.public_key.serialize()
+    }
+
+    /// Serialize `payload` to canonical CBOR, sign its digest, and wrap
+    /// both in a [`SignedEnvelope`].
+    pub fn sign<T: Serialize>(&self, payload: T) -> Result<SignedEnvelope<T>, KeychainError> {
+        let digest = hash_payload(&payload)?;
+        let secp = Secp256k1::new();
+        let message = Message::from_slice(&digest).map_err(|_| KeychainError::Encode)?;
+        let (recovery_id, signature) = secp
+            .sign_ecdsa_recoverable(&message, &self.secret_key)
+            .serialize_compact();
+
+        Ok(SignedEnvelope {
+            payload,
+            pubkey: self.public_key.serialize(),
+            signature,
+            recovery_id: recovery_id.to_i32() as u8,
+        })
+    }
+}
+
+/// Verify `envelope`'s signature against its embedded public key,
+/// returning the recovered public key on success. This confirms the
+/// envelope is internally consistent, but callers must still check the
+/// recovered key against their own allowlist.
+pub fn verify<T: Serialize>(envelope: &SignedEnvelope<T>) -> Result<PublicKey, KeychainError> {
+    let digest = hash_payload(&envelope.payload)?;
+    let secp = Secp256k1::new();
+    let message = Message::from_slice(&digest).map_err(|_| KeychainError::Encode)?;
+
+    let recovery_id =
+        RecoveryId::from_i32(envelope.recovery_id as i32).map_err(|_| KeychainError::InvalidSignature)?;
+    let signature = RecoverableSignature::from_compact(&envelope.signature, recovery_id)
+        .map_err(|_| KeychainError::InvalidSignature)?;
+
+    let recovered = secp
+        .recover_ecdsa(&message, &signature)
+        .map_err(|_| KeychainError::InvalidSignature)?;
+
+    let claimed =
+        PublicKey::from_slice(&envelope.pubkey).map_err(|_| KeychainError::InvalidSignature)?;
+    if recovered != claimed {
+        return Err(KeychainError::InvalidSignature);
+    }
+
+    secp.verify_ecdsa(&message, &signature.to_standard(), &claimed)
+        .map_err(|_| KeychainError::InvalidSignature)?;
+
+    Ok(recovered)
+}
+
+/// Verify `envelope` and additionally check the recovered signer
+/// against `allowlist`.
+pub fn verify_trusted<T: Serialize>(
+    envelope: &SignedEnvelope<T>,
+    allowlist: &[[u8; 33]],
+) -> Result<PublicKey, KeychainError> {
+    let signer = verify(envelope)?;
+    if !allowlist.contains(&signer.serialize()) {
+        return Err(KeychainError::UntrustedSigner);
+    }
+    Ok(signer)
+}
+
+fn hash_payload<T: Serialize>(payload: &T) -> Result<[u8; 32], KeychainError> {
+    let buf = serde_cbor::to_vec(payload).map_err(|_| KeychainError::Encode)?;
+    Ok(Sha256::digest(&buf).into())
+}
@@ -5,6 +5,9 @@ use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 pub mod attestation;
+pub mod keychain;
+pub mod policy;
+pub mod threading;
 
 pub const LOCAL_LISTEN_ADDRESS: &str = "0.0.0.0";
 
@@ -32,11 +35,14 @@ pub const KEEP_ARCH: &str = "keep-arch";
 pub const WASMLDR_BIND_PORT_CMD: &str = "wasmldr-bind-port";
 pub const WASMLDR_ADDR_CMD: &str = "wasmldr-addr";
 
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Backend {
     Nil,
     Sev,
     Sgx,
     Kvm,
+    SecureExecution,
 }
 pub type KeepList = Arc<Mutex<Vec<Keep>>>;
 
@@ -47,10 +53,14 @@ pub struct KeepMgr {
     pub keeps: Vec<Keep>,
 }
 
+/// A manager's advertisement that it can provision a Keep for the given
+/// `backend`. Managers should broadcast this wrapped in a
+/// `keychain::SignedEnvelope` so tenants can confirm the advertisement
+/// came from an authorized manager before trusting it.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct KeepContract {
     pub keepmgr: KeepMgr,
-    pub backend: String,
+    pub backend: Backend,
     //TODO - add duration of contract availability
 }
 
@@ -62,13 +72,17 @@ pub struct Wasmldr {
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Keep {
-    pub backend: String,
+    pub backend: Backend,
     pub kuuid: Uuid,
     pub state: String,
     pub wasmldr: Option<Wasmldr>,
     pub human_readable_info: Option<String>,
 }
 
+/// A tenant's wasm binary and accompanying metadata. A Keep should only
+/// load a `Workload` that arrived inside a `keychain::SignedEnvelope`
+/// signed by a key on its tenant allowlist, so a party on the path
+/// cannot swap in a different `wasm_binary`.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Workload {
     pub wasm_binary: Vec<u8>,
@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `policy` module lets operators pin which measurements and
+//! certificate issuers the Keep manager is willing to accept, as a
+//! `steward`-style TOML configuration, instead of leaving that
+//! decision implicit in server code. The Keep manager calls
+//! [`Policy::validate`] before transitioning a `Keep` out of
+//! `LoaderState::Indeterminate`.
+
+use codicon::Encoder;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use sev::certs::sev::ca::Certificate;
+use sev::certs::sev::Chain;
+use sev::Build;
+
+use crate::attestation::sev as sev_proto;
+use crate::attestation::sgx as sgx_proto;
+use crate::Backend;
+
+/// Byte length of an SGX quote header, preceding the 384-byte report
+/// body, per the Intel SGX ECDSA quote format.
+const QUOTE_HEADER_LEN: usize = 48;
+const MR_ENCLAVE_OFFSET: usize = QUOTE_HEADER_LEN + 64;
+const MR_SIGNER_OFFSET: usize = QUOTE_HEADER_LEN + 128;
+const ISV_PROD_ID_OFFSET: usize = QUOTE_HEADER_LEN + 256;
+const ISV_SVN_OFFSET: usize = QUOTE_HEADER_LEN + 258;
+const REPORT_BODY_END: usize = QUOTE_HEADER_LEN + 384;
+
+/// Acceptance rules for the AMD SEV backend.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SevPolicy {
+    /// AMD Secure Processor firmware builds this policy will accept.
+    pub allowed_builds: Vec<Build>,
+
+    /// `sev::launch::Start` policy bitmasks this policy will accept.
+    pub allowed_policy_bits: Vec<u32>,
+
+    /// AMD root (ARK) certificate this policy trusts, in AMD's native
+    /// binary certificate format.
+    pub trusted_root: Vec<u8>,
+
+    /// AMD intermediate (ASK) certificate this policy trusts, in AMD's
+    /// native binary certificate format.
+    pub trusted_intermediate: Vec<u8>,
+}
+
+/// Acceptance rules for the Intel SGX backend.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SgxPolicy {
+    /// Allowed enclave signers (MRSIGNER).
+    pub allowed_mrsigner: Vec<[u8; 32]>,
+
+    /// Allowed enclave measurements (MRENCLAVE).
+    pub allowed_mrenclave: Vec<[u8; 32]>,
+
+    /// The enclave product ID this policy expects.
+    pub product_id: u16,
+
+    /// The minimum ISV SVN this policy will accept.
+    pub min_isv_svn: u16,
+}
+
+/// A validation policy, typically loaded from a `steward`-style TOML
+/// configuration. A backend with no corresponding field configured has
+/// no acceptance rule and so rejects every message for that backend.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Policy {
+    pub sev: Option<SevPolicy>,
+    pub sgx: Option<SgxPolicy>,
+}
+
+/// The attestation message being validated against a [`Policy`], tagged
+/// with the backend protocol it belongs to. Message variants that carry
+/// nothing the policy checks, such as `Finish`, are accepted as they are.
+pub enum Message<'a> {
+    Sev(&'a sev_proto::Message),
+    Sgx(&'a sgx_proto::Message),
+}
+
+/// The reason a message failed to validate against a [`Policy`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum PolicyError {
+    /// No acceptance rule is configured for the given backend, or the
+    /// message belongs to a different backend's protocol.
+    NoPolicyForBackend(Backend),
+
+    /// The certificate chain's root or intermediate isn't the trusted
+    /// AMD signer this policy pins.
+    UnknownSigner,
+
+    /// The measurement isn't in the configured allowlist.
+    MeasurementNotAllowed,
+
+    /// The launch policy's flags are not one of the bitmasks this policy
+    /// allows.
+    PolicyBitForbidden,
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::NoPolicyForBackend(backend) => {
+                write!(f, "no policy configured for backend {:?}", backend)
+            }
+            PolicyError::UnknownSigner => write!(f, "certificate signer is not trusted"),
+            PolicyError::MeasurementNotAllowed => write!(f, "measurement is not in the allowlist"),
+            PolicyError::PolicyBitForbidden => write!(f, "policy bit is not permitted"),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl Policy {
+    /// Check `msg` against the acceptance rules configured for
+    /// `backend`, returning the specific reason for rejection on
+    /// failure.
+    pub fn validate(&self, backend: Backend, msg: &Message) -> Result<(), PolicyError> {
+        match (backend, msg) {
+            (Backend::Sev, Message::Sev(msg)) => self.validate_sev(msg),
+            (Backend::Sgx, Message::Sgx(msg)) => self.validate_sgx(msg),
+            (backend, _) => Err(PolicyError::NoPolicyForBackend(backend)),
+        }
+    }
+
+    fn validate_sev(&self, msg: &sev_proto::Message) -> Result<(), PolicyError> {
+        let policy = self
+            .sev
+            .as_ref()
+            .ok_or(PolicyError::NoPolicyForBackend(Backend::Sev))?;
+
+        match msg {
+            sev_proto::Message::CertificateChainNaples(chain)
+            | sev_proto::Message::CertificateChainRome(chain) => Self::validate_sev_chain(policy, chain),
+            sev_proto::Message::LaunchStart(start) => {
+                let bits = start.policy.flags.bits() as u32;
+                if !policy.allowed_policy_bits.contains(&bits) {
+                    return Err(PolicyError::PolicyBitForbidden);
+                }
+                Ok(())
+            }
+            sev_proto::Message::Measurement(measurement) => {
+                if !policy.allowed_builds.contains(&measurement.build) {
+                    return Err(PolicyError::MeasurementNotAllowed);
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Compare the chain's AMD root (ARK) and intermediate (ASK)
+    /// certificates, encoded to their native AMD binary format, against
+    /// the ones this policy pins.
+    fn validate_sev_chain(policy: &SevPolicy, chain: &Chain) -> Result<(), PolicyError> {
+        let root = Self::encode_cert(&chain.ca.ark)?;
+        let intermediate = Self::encode_cert(&chain.ca.ask)?;
+
+        if root != policy.trusted_root || intermediate != policy.trusted_intermediate {
+            return Err(PolicyError::UnknownSigner);
+        }
+        Ok(())
+    }
+
+    fn encode_cert(cert: &Certificate) -> Result<Vec<u8>, PolicyError> {
+        let mut encoded = Vec::new();
+        cert.encode(&mut encoded, ())
+            .map_err(|_| PolicyError::UnknownSigner)?;
+        Ok(encoded)
+    }
+
+    fn validate_sgx(&self, msg: &sgx_proto::Message) -> Result<(), PolicyError> {
+        let policy = self
+            .sgx
+            .as_ref()
+            .ok_or(PolicyError::NoPolicyForBackend(Backend::Sgx))?;
+
+        match msg {
+            sgx_proto::Message::Quote { quote } => Self::validate_sgx_quote(policy, quote),
+            _ => Ok(()),
+        }
+    }
+
+    /// Parse MRENCLAVE, MRSIGNER, the ISV product ID, and the ISV SVN
+    /// out of the ECDSA quote's report body and compare them against
+    /// this policy's allowlist.
+    fn validate_sgx_quote(policy: &SgxPolicy, quote: &[u8]) -> Result<(), PolicyError> {
+        if quote.len() < REPORT_BODY_END {
+            return Err(PolicyError::MeasurementNotAllowed);
+        }
+
+        let mut mr_enclave = [0u8; 32];
+        mr_enclave.copy_from_slice(&quote[MR_ENCLAVE_OFFSET..MR_ENCLAVE_OFFSET + 32]);
+
+        let mut mr_signer = [0u8; 32];
+        mr_signer.copy_from_slice(&quote[MR_SIGNER_OFFSET..MR_SIGNER_OFFSET + 32]);
+
+        let isv_prod_id = u16::from_le_bytes([quote[ISV_PROD_ID_OFFSET], quote[ISV_PROD_ID_OFFSET + 1]]);
+        let isv_svn = u16::from_le_bytes([quote[ISV_SVN_OFFSET], quote[ISV_SVN_OFFSET + 1]]);
+
+        if !policy.allowed_mrsigner.contains(&mr_signer) || !policy.allowed_mrenclave.contains(&mr_enclave) {
+            return Err(PolicyError::MeasurementNotAllowed);
+        }
+        if isv_prod_id != policy.product_id || isv_svn < policy.min_isv_svn {
+            return Err(PolicyError::MeasurementNotAllowed);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sev::{Build, Version};
+
+    fn build(major: u8, minor: u8, build: u8) -> Build {
+        Build {
+            version: Version { major, minor },
+            build,
+        }
+    }
+
+    fn sev_policy_with_build(allowed: Build) -> Policy {
+        Policy {
+            sev: Some(SevPolicy {
+                allowed_builds: vec![allowed],
+                allowed_policy_bits: vec![],
+                trusted_root: vec![],
+                trusted_intermediate: vec![],
+            }),
+            sgx: None,
+        }
+    }
+
+    #[test]
+    fn validate_sev_accepts_allowed_build() {
+        let allowed = build(1, 2, 3);
+        let policy = sev_policy_with_build(allowed);
+        let msg = sev_proto::Message::Measurement(sev_proto::Measurement {
+            build: allowed,
+            measurement: sev::launch::sev::Measurement {
+                measure: [0u8; 32],
+                mnonce: [0u8; 16],
+            },
+            nonce: [0u8; 32],
+        });
+
+        assert_eq!(policy.validate(Backend::Sev, &Message::Sev(&msg)), Ok(()));
+    }
+
+    #[test]
+    fn validate_sev_rejects_unlisted_build() {
+        let policy = sev_policy_with_build(build(1, 2, 3));
+        let msg = sev_proto::Message::Measurement(sev_proto::Measurement {
+            build: build(9, 9, 9),
+            measurement: sev::launch::sev::Measurement {
+                measure: [0u8; 32],
+                mnonce: [0u8; 16],
+            },
+            nonce: [0u8; 32],
+        });
+
+        assert_eq!(
+            policy.validate(Backend::Sev, &Message::Sev(&msg)),
+            Err(PolicyError::MeasurementNotAllowed)
+        );
+    }
+
+    fn sgx_policy() -> Policy {
+        Policy {
+            sev: None,
+            sgx: Some(SgxPolicy {
+                allowed_mrsigner: vec![[7u8; 32]],
+                allowed_mrenclave: vec![[8u8; 32]],
+                product_id: 42,
+                min_isv_svn: 1,
+            }),
+        }
+    }
+
+    fn quote_with(mr_enclave: [u8; 32], mr_signer: [u8; 32], isv_prod_id: u16, isv_svn: u16) -> Vec<u8> {
+        let mut quote = vec![0u8; REPORT_BODY_END];
+        quote[MR_ENCLAVE_OFFSET..MR_ENCLAVE_OFFSET + 32].copy_from_slice(&mr_enclave);
+        quote[MR_SIGNER_OFFSET..MR_SIGNER_OFFSET + 32].copy_from_slice(&mr_signer);
+        quote[ISV_PROD_ID_OFFSET..ISV_PROD_ID_OFFSET + 2].copy_from_slice(&isv_prod_id.to_le_bytes());
+        quote[ISV_SVN_OFFSET..ISV_SVN_OFFSET + 2].copy_from_slice(&isv_svn.to_le_bytes());
+        quote
+    }
+
+    #[test]
+    fn validate_sgx_accepts_allowed_quote() {
+        let policy = sgx_policy();
+        let quote = quote_with([8u8; 32], [7u8; 32], 42, 1);
+        let msg = sgx_proto::Message::Quote { quote };
+
+        assert_eq!(policy.validate(Backend::Sgx, &Message::Sgx(&msg)), Ok(()));
+    }
+
+    #[test]
+    fn validate_sgx_rejects_unlisted_mrenclave() {
+        let policy = sgx_policy();
+        let quote = quote_with([0u8; 32], [7u8; 32], 42, 1);
+        let msg = sgx_proto::Message::Quote { quote };
+
+        assert_eq!(
+            policy.validate(Backend::Sgx, &Message::Sgx(&msg)),
+            Err(PolicyError::MeasurementNotAllowed)
+        );
+    }
+}
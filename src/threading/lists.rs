@@ -3,7 +3,25 @@
 use super::super::*;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
 pub type KeepList = Arc<Mutex<Vec<Keep>>>;
 pub type ContractList = Arc<Mutex<Vec<KeepContract>>>;
 pub type KeepLdrConnList = Arc<Mutex<Vec<KeepLdrConnection>>>;
+
+/// The compressed secp256k1 public keys of tenants authorized to sign
+/// `Workload`s, held alongside `ContractList` so a Keep can check a
+/// `keychain::SignedEnvelope`'s recovered signer before loading.
+pub type SignerAllowList = Arc<Mutex<Vec<[u8; 33]>>>;
+
+/// Tracks the per-connection state the keep manager needs while
+/// carrying out the SEV RCAR attestation handshake with a tenant.
+///
+/// `challenge_nonce` holds the nonce issued in `attestation::sev::Message::Challenge`
+/// until it is consumed by a matching `Measurement`, so that each
+/// nonce can only be redeemed exactly once.
+#[derive(Clone)]
+pub struct KeepLdrConnection {
+    pub kuuid: Uuid,
+    pub challenge_nonce: Option<[u8; 32]>,
+}